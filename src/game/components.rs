@@ -18,6 +18,9 @@ pub struct Tile;
 #[derive(Component)]
 pub struct Collision;
 
+#[derive(Component)]
+pub struct ScoreText;
+
 #[derive(Copy, Clone, Component, PartialEq, Eq, Hash)]
 pub struct Pos {
     pub x: u32,
@@ -25,7 +28,19 @@ pub struct Pos {
 }
 
 #[derive(Component)]
-pub struct Age(pub u32);
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    pub fn square(x: f32) -> Self {
+        Self {
+            width: x,
+            height: x,
+        }
+    }
+}
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Direction {
@@ -48,7 +63,6 @@ impl Direction {
 
 #[derive(Component)]
 pub struct SnakeMeta {
-    pub len: u32,
     pub dir: Direction,
     // direction snake used to reach current position
     // used to forbid moving backwards by changing direction twice
@@ -9,23 +9,43 @@ pub struct Scene {
     pub y_size: u32,
 }
 
+pub struct SnakeSegments(pub Vec<Entity>);
+
+pub struct LastTailPosition(pub Option<Pos>);
+
+pub struct GameOverEvent;
+
+pub struct Score(pub u32);
+
+pub struct FoodSpawnTimer(pub Timer);
+
+const MAX_FOOD_COUNT: usize = 5;
+
 pub struct GamePlugin;
 
 static POST_SNAKE: &str = "post_snake";
-static POST_ALL: &str = "post_all";
 
 impl GamePlugin {
     fn eat_food(
         mut commands: Commands,
-        mut snake_query: Query<(&mut SnakeMeta, &Pos)>,
+        asset_server: Res<AssetServer>,
+        mut segments: ResMut<SnakeSegments>,
+        last_tail_position: Res<LastTailPosition>,
+        mut score: ResMut<Score>,
+        snake_query: Query<&Pos, With<SnakeMeta>>,
         food_query: Query<(Entity, &Pos), With<Food>>,
     ) {
-        let (mut snake_meta, snake_pos) = snake_query.single_mut();
+        let snake_pos = snake_query.single();
 
         for (et, food_pos) in food_query.iter() {
             if snake_pos == food_pos {
                 commands.entity(et).despawn();
-                snake_meta.len += 1;
+                score.0 += 1;
+                if let Some(pos) = last_tail_position.0 {
+                    segments
+                        .0
+                        .push(spawn_snake_body(&mut commands, &asset_server, &pos));
+                }
                 break;
             }
         }
@@ -34,15 +54,22 @@ impl GamePlugin {
     fn respawn_food(
         mut commands: Commands,
         asset_server: Res<AssetServer>,
+        time: Res<Time>,
+        mut spawn_timer: ResMut<FoodSpawnTimer>,
         food_query: Query<(Entity, &Pos), With<Food>>,
         collision_query: Query<&Pos, With<Collision>>,
         scene: Res<Scene>,
     ) {
-        if !food_query.is_empty() {
+        if !spawn_timer.0.tick(time.delta()).just_finished() {
+            return;
+        }
+
+        if food_query.iter().count() >= MAX_FOOD_COUNT {
             return;
         }
 
-        let occupied_pos: HashSet<Pos> = collision_query.iter().copied().collect();
+        let mut occupied_pos: HashSet<Pos> = collision_query.iter().copied().collect();
+        occupied_pos.extend(food_query.iter().map(|(_, pos)| *pos));
         if occupied_pos.len() == (scene.x_size * scene.y_size) as usize {
             // scene full
             return;
@@ -51,7 +78,7 @@ impl GamePlugin {
         let mut rng = thread_rng();
         let num_attempts = 100;
         let mut food_spawned = false;
-        for _ in [0..num_attempts] {
+        for _ in 0..num_attempts {
             let x: u32 = rng.gen_range(0..scene.x_size);
             let y: u32 = rng.gen_range(0..scene.y_size);
             if !occupied_pos.contains(&Pos { x, y }) {
@@ -73,16 +100,30 @@ impl GamePlugin {
         }
     }
 
-    fn update_position(mut query: Query<(&mut Transform, &Pos), Changed<Pos>>, scene: Res<Scene>) {
-        // offset shows distance from border to the center of the scene
-        let offset_x = (scene.x_size as f32 - 1.0) * (TILE_SIZE as f32) / 2.0;
-        let offset_y = (scene.y_size as f32 - 1.0) * (TILE_SIZE as f32) / 2.0;
+    fn size_scaling(windows: Res<Windows>, scene: Res<Scene>, mut query: Query<(&Size, &mut Transform)>) {
+        let window = windows.get_primary().unwrap();
+        for (size, mut transform) in query.iter_mut() {
+            transform.scale = Vec3::new(
+                size.width / scene.x_size as f32 * window.width(),
+                size.height / scene.y_size as f32 * window.height(),
+                1.0,
+            );
+        }
+    }
+
+    fn position_translation(windows: Res<Windows>, scene: Res<Scene>, mut query: Query<(&Pos, &mut Transform)>) {
+        fn convert(pos: f32, bound_window: f32, bound_game: f32) -> f32 {
+            let tile_size = bound_window / bound_game;
+            pos / bound_game * bound_window - (bound_window / 2.0) + (tile_size / 2.0)
+        }
 
-        for (mut transform, pos) in query.iter_mut() {
-            // in bevy for 2D x=0,y=0 points to the center of the screen
-            // we subtract offset so that center of the scene matches center of the screen
-            transform.translation.x = (pos.x * TILE_SIZE) as f32 - offset_x;
-            transform.translation.y = (pos.y * TILE_SIZE) as f32 - offset_y;
+        let window = windows.get_primary().unwrap();
+        for (pos, mut transform) in query.iter_mut() {
+            transform.translation = Vec3::new(
+                convert(pos.x as f32, window.width(), scene.x_size as f32),
+                convert(pos.y as f32, window.height(), scene.y_size as f32),
+                transform.translation.z,
+            );
         }
     }
 
@@ -108,34 +149,52 @@ impl GamePlugin {
     }
 
     fn move_snake(
-        commands: Commands,
-        asset_server: Res<AssetServer>,
-        mut query: Query<(&mut SnakeMeta, &mut Pos)>,
+        segments: Res<SnakeSegments>,
+        mut last_tail_position: ResMut<LastTailPosition>,
+        mut snake_query: Query<&mut SnakeMeta>,
+        mut positions: Query<&mut Pos>,
     ) {
-        let (mut snake_meta, mut pos) = query.single_mut();
-        let old_pos = *pos;
+        let mut snake_meta = snake_query.single_mut();
+        let segment_positions = segments
+            .0
+            .iter()
+            .map(|entity| *positions.get_mut(*entity).unwrap())
+            .collect::<Vec<Pos>>();
+
+        let head_entity = *segments.0.first().unwrap();
+        let mut head_pos = *positions.get_mut(head_entity).unwrap();
         match snake_meta.dir {
             Direction::Up => {
-                pos.y += 1;
+                head_pos.y += 1;
             }
             Direction::Down => {
-                pos.y -= 1;
+                head_pos.y -= 1;
             }
             Direction::Left => {
-                pos.x -= 1;
+                head_pos.x -= 1;
             }
             Direction::Right => {
-                pos.x += 1;
+                head_pos.x += 1;
             }
         }
         snake_meta.prev_dir = snake_meta.dir;
+        *positions.get_mut(head_entity).unwrap() = head_pos;
 
-        spawn_snake_body(commands, asset_server, &old_pos);
+        segment_positions
+            .iter()
+            .zip(segments.0.iter().skip(1))
+            .for_each(|(pos, segment)| {
+                *positions.get_mut(*segment).unwrap() = *pos;
+            });
+
+        *last_tail_position = LastTailPosition(Some(*segment_positions.last().unwrap()));
     }
 
     fn check_snake_collides(
+        mut game_over_writer: EventWriter<GameOverEvent>,
         snake_query: Query<(Entity, &SnakeMeta, &Pos), Changed<Pos>>,
         collision_query: Query<(Entity, &Pos), With<Collision>>,
+        scene: Res<Scene>,
     ) {
         if snake_query.is_empty() {
             // currently system runs each tick and we are only interested in ticks
@@ -143,25 +202,43 @@ impl GamePlugin {
             return;
         }
         let (snake_id, _, snake_pos) = snake_query.single();
+
+        if snake_pos.x >= scene.x_size || snake_pos.y >= scene.y_size {
+            game_over_writer.send(GameOverEvent);
+            return;
+        }
+
         for (ent_id, ent_pos) in collision_query.iter() {
             if snake_pos == ent_pos && snake_id != ent_id {
-                println!("failed");
+                game_over_writer.send(GameOverEvent);
+                break;
             }
         }
     }
 
-    fn despawn_old(
+    fn game_over(
         mut commands: Commands,
-        mut body_query: Query<(Entity, &SnakeBody, &mut Age)>,
-        snake_query: Query<&SnakeMeta>,
+        asset_server: Res<AssetServer>,
+        mut game_over_reader: EventReader<GameOverEvent>,
+        mut score: ResMut<Score>,
+        food_query: Query<Entity, With<Food>>,
+        body_query: Query<Entity, With<SnakeBody>>,
+        snake_query: Query<Entity, With<Snake>>,
     ) {
-        let snake_meta = snake_query.single();
-        for (entity, _, mut age) in body_query.iter_mut() {
-            age.as_mut().0 += 1;
-            if age.0 + 1 == snake_meta.len {
-                commands.entity(entity).despawn();
-            }
+        if game_over_reader.iter().count() == 0 {
+            return;
+        }
+
+        for entity in food_query
+            .iter()
+            .chain(body_query.iter())
+            .chain(snake_query.iter())
+        {
+            commands.entity(entity).despawn();
         }
+
+        score.0 = 0;
+        Self::spawn_snake(commands, asset_server);
     }
 
     fn control_snake(mut snake_query: Query<&mut SnakeMeta>, inputs: Res<Input<KeyCode>>) {
@@ -183,64 +260,129 @@ impl GamePlugin {
         }
     }
 
+    fn spawn_score_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands
+            .spawn_bundle(TextBundle {
+                style: Style {
+                    align_self: AlignSelf::FlexEnd,
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(5.0),
+                        left: Val::Px(5.0),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                text: Text::from_section(
+                    "Score: 0",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                ..Default::default()
+            })
+            .insert(ScoreText);
+
+        commands.insert_resource(Score(0));
+    }
+
+    fn update_score_text(score: Res<Score>, mut query: Query<&mut Text, With<ScoreText>>) {
+        if !score.is_changed() {
+            return;
+        }
+
+        for mut text in query.iter_mut() {
+            text.sections[0].value = format!("Score: {}", score.0);
+        }
+    }
+
     fn spawn_snake(mut commands: Commands, asset_server: Res<AssetServer>) {
         let snake_image = asset_server.load("images/snake.png");
-        commands
+        let snake_entity = commands
             .spawn()
             .insert(Snake)
             .insert(SnakeMeta {
-                len: 4,
                 dir: Direction::Right,
                 prev_dir: Direction::Right,
             })
             .insert(Collision)
             .insert(Pos { x: 5, y: 5 })
+            .insert(Size::square(1.0))
             .insert_bundle(SpriteBundle {
                 texture: snake_image,
+                sprite: Sprite {
+                    custom_size: Some(Vec2::ONE),
+                    ..Default::default()
+                },
                 ..Default::default()
-            });
+            })
+            .id();
+
+        // trail the initial body behind the head, opposite its starting direction
+        let mut segments = vec![snake_entity];
+        for x in (2..5).rev() {
+            segments.push(spawn_snake_body(
+                &mut commands,
+                &asset_server,
+                &Pos { x, y: 5 },
+            ));
+        }
+
+        commands.insert_resource(SnakeSegments(segments));
+        commands.insert_resource(LastTailPosition(None));
     }
 }
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(Self::create_basic_scene)
+        app.add_event::<GameOverEvent>()
+            .insert_resource(FoodSpawnTimer(Timer::from_seconds(1.0, true)))
+            .add_startup_system(Self::create_basic_scene)
             .add_startup_system(Self::spawn_snake)
+            .add_startup_system(Self::spawn_score_ui)
             .add_system(bevy::input::system::exit_on_esc_system)
             .add_system(Self::control_snake.before(Self::move_snake))
+            .add_system(Self::update_score_text)
             .add_system_set(
                 SystemSet::new()
                     .with_run_criteria(FixedTimestep::step(0.2))
                     .with_system(Self::move_snake)
-                    .with_system(Self::eat_food.after(Self::move_snake))
-                    .with_system(Self::despawn_old.after(Self::eat_food)),
+                    .with_system(Self::eat_food.after(Self::move_snake)),
             )
             // snake segments fully [de]spawn in the end of update stage,
             // so we can safely spawn new objects only in new stage
             .add_stage_after(CoreStage::Update, POST_SNAKE, SystemStage::parallel())
             .add_system_to_stage(POST_SNAKE, Self::respawn_food)
             .add_system_to_stage(POST_SNAKE, Self::check_snake_collides)
-            .add_stage_after(POST_SNAKE, POST_ALL, SystemStage::parallel())
-            .add_system_to_stage(POST_ALL, Self::update_position);
+            .add_system_to_stage(POST_SNAKE, Self::game_over.after(Self::check_snake_collides))
+            .add_system_to_stage(CoreStage::PostUpdate, Self::size_scaling)
+            .add_system_to_stage(CoreStage::PostUpdate, Self::position_translation);
     }
 }
 
 const Z_SNAKE: f32 = 10.0;
 const Z_FOOD: f32 = 10.0;
 
-fn spawn_snake_body(mut commands: Commands, asset_server: Res<AssetServer>, pos: &Pos) {
+fn spawn_snake_body(commands: &mut Commands, asset_server: &Res<AssetServer>, pos: &Pos) -> Entity {
     let body_image = asset_server.load("images/snake.png");
     commands
         .spawn()
         .insert(SnakeBody)
-        .insert(Age(0))
         .insert(Pos { x: pos.x, y: pos.y })
         .insert(Collision)
+        .insert(Size::square(1.0))
         .insert_bundle(SpriteBundle {
             texture: body_image,
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, Z_SNAKE)),
+            sprite: Sprite {
+                custom_size: Some(Vec2::ONE),
+                ..Default::default()
+            },
             ..Default::default()
-        });
+        })
+        .id()
 }
 
 fn spawn_food(commands: &mut Commands, asset_server: &Res<AssetServer>, pos: &Pos) {
@@ -249,9 +391,14 @@ fn spawn_food(commands: &mut Commands, asset_server: &Res<AssetServer>, pos: &Po
         .spawn()
         .insert(Food)
         .insert(Pos { x: pos.x, y: pos.y })
+        .insert(Size::square(0.8))
         .insert_bundle(SpriteBundle {
             texture: food_image,
             transform: Transform::from_translation(Vec3::new(0.0, 0.0, Z_FOOD)),
+            sprite: Sprite {
+                custom_size: Some(Vec2::ONE),
+                ..Default::default()
+            },
             ..Default::default()
         });
 }
@@ -292,9 +439,13 @@ impl TileFactory {
 
         let mut ent_cmd = commands.spawn_bundle(SpriteBundle {
             texture: material.clone(),
+            sprite: Sprite {
+                custom_size: Some(Vec2::ONE),
+                ..Default::default()
+            },
             ..Default::default()
         });
-        ent_cmd.insert_bundle((Tile, pos));
+        ent_cmd.insert_bundle((Tile, pos, Size::square(1.0)));
         if tile.has_collision() {
             ent_cmd.insert(Collision);
         }
@@ -302,4 +453,3 @@ impl TileFactory {
     }
 }
 
-const TILE_SIZE: u32 = 32;